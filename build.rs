@@ -0,0 +1,37 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let wordlist_path =
+        env::var("SANAMAHTI_WORDLIST").unwrap_or_else(|_| "wordlist_fin.txt".to_string());
+
+    println!("cargo:rerun-if-changed={wordlist_path}");
+    println!("cargo:rerun-if-env-changed=SANAMAHTI_WORDLIST");
+
+    let contents = fs::read_to_string(&wordlist_path)
+        .unwrap_or_else(|e| panic!("Failed to open wordlist at {wordlist_path}: {e}"));
+
+    let words = contents
+        .lines()
+        .map(|line| {
+            line.to_lowercase()
+                .chars()
+                // NOTE: skip the BOM
+                .skip_while(|c| *c == '\u{feff}')
+                .collect::<String>()
+        })
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>();
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let dest_path = Path::new(&out_dir).join("words.rs");
+
+    let mut generated = String::from("pub static WORD_LIST: &[&str] = &[\n");
+    for word in &words {
+        generated.push_str(&format!("    {word:?},\n"));
+    }
+    generated.push_str("];\n");
+
+    fs::write(&dest_path, generated).expect("Failed to write generated word list");
+}
@@ -1,45 +1,44 @@
 #![feature(lazy_cell)]
 
-use std::collections::{HashSet, VecDeque};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::LazyLock;
 
-static WORDS: LazyLock<LetterTree> = LazyLock::new(|| {
-    let wordfile = File::open("wordlist_fin.txt").expect("Failed to open wordlist");
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+
+// Generated by build.rs: `pub static WORD_LIST: &[&str] = &[...]`, baked in from the
+// wordlist at compile time so the crate has no runtime filesystem dependency.
+include!(concat!(env!("OUT_DIR"), "/words.rs"));
+
+static WORDS: LazyLock<LetterTree<()>> = LazyLock::new(|| {
     let mut tree = LetterTree::new();
-    BufReader::new(wordfile)
-        .lines()
-        .map_while(Result::ok)
-        .for_each(|w| {
-            tree.insert(
-                &w.to_lowercase()
-                    .chars()
-                    // NOTE: skip the BOM
-                    .skip_while(|c| *c == '\u{feff}')
-                    .collect::<String>(),
-            );
-        });
+    WORD_LIST.iter().for_each(|w| tree.insert(w, ()));
     tree
 });
 
 /// Represents a node of a tree. Apart from the root node, each node represents one character
 /// in a word.
 ///
-/// Each path in the tree that ends in a node that has the [`LetterTree::is_word`]-field set to [`true`]
-/// represents a word.
+/// Each path in the tree that ends in a node that has the [`LetterTree::value`]-field set to
+/// [`Some`] represents a word, and the field carries an arbitrary payload `V` for that word
+/// (e.g. a frequency count or a Scrabble-style point value).
 ///
 /// New words can be inserted with the [`LetterTree::insert`]-method.
 ///
-/// The [`LetterTree::word_status`]-method is used get the [`Status`] of a specific word.
-#[derive(Debug, Clone, Default)]
-pub struct LetterTree {
-    /// The character represented by the node
-    pub value: Option<char>,
-    /// Whether or not the node is the last letter of a valid word.
-    pub is_word: bool,
-    /// The children of the node ie. all the possible continuations for the path that produce a valid word
-    pub leaves: Vec<LetterTree>,
+/// The [`LetterTree::word_status`]-method is used get the [`Status`] of a specific word, and
+/// [`LetterTree::get`] looks up the payload stored for a word.
+#[derive(Debug, Clone)]
+pub struct LetterTree<V> {
+    /// The payload stored if this node is the last letter of a valid word, `None` otherwise.
+    pub value: Option<V>,
+    /// The children of the node, keyed by the character that continues the path to them.
+    pub children: FxHashMap<char, Box<LetterTree<V>>>,
+}
+
+impl<V> Default for LetterTree<V> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Represents the status for a word (path) in the [`LetterTree`]
@@ -66,39 +65,35 @@ fn neighbours(pos: (i32, i32)) -> Vec<(i32, i32)> {
         .collect::<Vec<_>>()
 }
 
-impl LetterTree {
+impl<V> LetterTree<V> {
     /// Construct a new node.
     pub fn new() -> Self {
         Self {
             value: None,
-            is_word: false,
-            leaves: Vec::new(),
+            children: FxHashMap::default(),
         }
     }
 
-    /// Insert a new word to the tree.
-    pub fn insert(&mut self, word: &str) {
+    /// Insert a new word into the tree, storing `value` at its terminal node.
+    pub fn insert(&mut self, word: &str, value: V) {
         if word.is_empty() {
             return;
         }
 
-        let mut word = word.chars().peekable();
-        let letter = word.next().expect("Word is nonempty");
+        let mut chars = word.chars();
+        let letter = chars.next().expect("Word is nonempty");
+        let rest = chars.collect::<String>();
 
-        let leaf = if let Some(leaf) = self.leaves.iter_mut().find(|l| l.value == Some(letter)) {
-            leaf
-        } else {
-            self.leaves.push(LetterTree {
-                value: Some(letter),
-                is_word: word.peek().is_none(),
-                leaves: Vec::new(),
-            });
-            self.leaves
-                .last_mut()
-                .expect("Post-push leaves is nonempty")
-        };
+        let leaf = self
+            .children
+            .entry(letter)
+            .or_insert_with(|| Box::new(LetterTree::new()));
 
-        leaf.insert(&word.collect::<String>());
+        if rest.is_empty() {
+            leaf.value = Some(value);
+        } else {
+            leaf.insert(&rest, value);
+        }
     }
 
     /// Get the [`Status`] of a specific word in the tree.
@@ -106,20 +101,84 @@ impl LetterTree {
         let mut word = word.chars();
 
         let Some(letter) = word.next() else {
-            if self.is_word {
+            if self.value.is_some() {
                 return Status::Word;
-            } else if !self.leaves.is_empty() {
+            } else if !self.children.is_empty() {
                 return Status::Possible;
             }
             return Status::Impossible;
         };
 
-        if let Some(leaf) = self.leaves.iter().find(|l| l.value == Some(letter)) {
+        if let Some(leaf) = self.children.get(&letter) {
             leaf.word_status(&word.collect::<String>())
         } else {
             Status::Impossible
         }
     }
+
+    /// Get the value stored for `word`, or [`None`] if `word` isn't in the tree.
+    pub fn get(&self, word: &str) -> Option<&V> {
+        let mut word = word.chars();
+
+        let Some(letter) = word.next() else {
+            return self.value.as_ref();
+        };
+
+        self.children.get(&letter)?.get(&word.collect::<String>())
+    }
+
+    /// Find every dictionary word within Levenshtein distance `max_distance` of `query`,
+    /// paired with its distance.
+    ///
+    /// Uses the classic trie + DP-row technique: one row of the edit-distance matrix is
+    /// carried per trie depth instead of recomputing the whole matrix per word, and an
+    /// entire subtree is pruned as soon as its row's minimum exceeds `max_distance`, since
+    /// distances only grow further down the trie.
+    pub fn fuzzy_matches(&self, query: &str, max_distance: usize) -> Vec<(String, usize)> {
+        let query = query.chars().collect::<Vec<_>>();
+        let root_row = (0..=query.len()).collect::<Vec<_>>();
+        let mut matches = Vec::new();
+
+        for (&letter, leaf) in &self.children {
+            leaf.fuzzy_matches_inner(letter, &query, &root_row, String::new(), max_distance, &mut matches);
+        }
+
+        matches
+    }
+
+    fn fuzzy_matches_inner(
+        &self,
+        letter: char,
+        query: &[char],
+        parent_row: &[usize],
+        mut word: String,
+        max_distance: usize,
+        matches: &mut Vec<(String, usize)>,
+    ) {
+        word.push(letter);
+
+        let mut row = Vec::with_capacity(parent_row.len());
+        row.push(parent_row[0] + 1);
+        for (i, q) in query.iter().enumerate() {
+            let substitution_cost = usize::from(*q != letter);
+            row.push(
+                (parent_row[i + 1] + 1)
+                    .min(row[i] + 1)
+                    .min(parent_row[i] + substitution_cost),
+            );
+        }
+
+        let distance = *row.last().expect("Row is nonempty");
+        if self.value.is_some() && distance <= max_distance {
+            matches.push((word.clone(), distance));
+        }
+
+        if *row.iter().min().expect("Row is nonempty") <= max_distance {
+            for (&child_letter, leaf) in &self.children {
+                leaf.fuzzy_matches_inner(child_letter, query, &row, word.clone(), max_distance, matches);
+            }
+        }
+    }
 }
 
 /// Get all possible words that can be represented as
@@ -127,10 +186,10 @@ impl LetterTree {
 ///
 /// Panics if the grid size is not 4x4.
 ///
-/// Internally a BFS is performed, starting from each tile
-/// on the grid. The BFS is terminated early for a branch
-/// if there [`LetterTree::word_status`] returns [`Status::Impossible`]
-/// for that word.
+/// Internally a BFS is performed independently from each of the 16 starting tiles, run
+/// concurrently across threads since the searches share no state besides the final
+/// found-word set. Each search is terminated early for a branch if
+/// [`LetterTree::word_status`] returns [`Status::Impossible`] for that word.
 ///
 /// Returns a [`Vec<String>`] of the found words that is sorted
 /// by word length in ascending order.
@@ -138,40 +197,181 @@ pub fn solve(grid: Vec<Vec<char>>) -> Vec<String> {
     assert!(grid.len() == 4, "Invalid grid size");
     assert!(grid[0].len() == 4, "Invalid grid size");
 
-    let mut s = (0..4)
+    let starts = (0..4)
         .flat_map(|x| (0..4).map(|y| (x, y)).collect::<Vec<_>>())
-        .map(|c| (c, vec![c]))
-        .collect::<VecDeque<_>>();
-    let mut found = HashSet::new();
+        .collect::<Vec<_>>();
 
-    while let Some((pos, path)) = s.pop_front() {
-        let word = path
-            .iter()
-            .map(|(x, y)| grid[(*y) as usize][(*x) as usize])
-            .collect::<String>();
+    let found = starts
+        .into_par_iter()
+        .map(|start| {
+            let mut s = VecDeque::new();
+            s.push_back((start, vec![start]));
+            let mut found = HashSet::new();
+
+            while let Some((pos, path)) = s.pop_front() {
+                let word = path
+                    .iter()
+                    .map(|(x, y)| grid[(*y) as usize][(*x) as usize])
+                    .collect::<String>();
 
-        match WORDS.word_status(&word) {
-            Status::Word => {
-                if word.len() > 2 {
-                    found.insert(word);
+                match WORDS.word_status(&word) {
+                    Status::Word => {
+                        if word.len() > 2 {
+                            found.insert(word);
+                        }
+                    }
+                    Status::Impossible => {
+                        continue;
+                    }
+                    Status::Possible => {}
                 }
+
+                neighbours(pos).iter().for_each(|n| {
+                    if !path.contains(n) {
+                        let mut newpath = path.clone();
+                        newpath.push(*n);
+                        s.push_back((*n, newpath));
+                    }
+                });
             }
-            Status::Impossible => {
-                continue;
+
+            found
+        })
+        .reduce(HashSet::new, |mut a, b| {
+            a.extend(b);
+            a
+        });
+
+    let mut found_vec = found.into_iter().collect::<Vec<_>>();
+    found_vec.sort_by_key(|w| w.chars().count());
+    found_vec
+}
+
+/// Like [`solve`], but pairs every found word with the sequence of grid coordinates that
+/// spells it out, for callers that need to highlight the path on a board.
+///
+/// When the same word is reachable by multiple paths, the first one found is kept.
+///
+/// Panics if the grid size is not 4x4.
+pub fn solve_with_paths(grid: Vec<Vec<char>>) -> Vec<(String, Vec<(i32, i32)>)> {
+    assert!(grid.len() == 4, "Invalid grid size");
+    assert!(grid[0].len() == 4, "Invalid grid size");
+
+    let starts = (0..4)
+        .flat_map(|x| (0..4).map(|y| (x, y)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let found = starts
+        .into_par_iter()
+        .map(|start| {
+            let mut s = VecDeque::new();
+            s.push_back((start, vec![start]));
+            let mut found: HashMap<String, Vec<(i32, i32)>> = HashMap::new();
+
+            while let Some((pos, path)) = s.pop_front() {
+                let word = path
+                    .iter()
+                    .map(|(x, y)| grid[(*y) as usize][(*x) as usize])
+                    .collect::<String>();
+
+                match WORDS.word_status(&word) {
+                    Status::Word => {
+                        if word.len() > 2 {
+                            found.entry(word).or_insert_with(|| path.clone());
+                        }
+                    }
+                    Status::Impossible => {
+                        continue;
+                    }
+                    Status::Possible => {}
+                }
+
+                neighbours(pos).iter().for_each(|n| {
+                    if !path.contains(n) {
+                        let mut newpath = path.clone();
+                        newpath.push(*n);
+                        s.push_back((*n, newpath));
+                    }
+                });
             }
-            Status::Possible => {}
-        }
 
-        neighbours(pos).iter().for_each(|n| {
-            if !path.contains(n) {
-                let mut newpath = path.clone();
-                newpath.push(*n);
-                s.push_back((*n, newpath));
+            found
+        })
+        .reduce(HashMap::new, |mut a, b| {
+            for (word, path) in b {
+                a.entry(word).or_insert(path);
             }
+            a
         });
-    }
 
     let mut found_vec = found.into_iter().collect::<Vec<_>>();
-    found_vec.sort_by_key(|w| w.chars().count());
+    found_vec.sort_by_key(|(w, _)| w.chars().count());
     found_vec
 }
+
+/// A length-to-points table used to [`ScoreTable::score`] words for Boggle-style play.
+///
+/// The default implements the common Boggle mapping (3-4 letters = 1 point, 5 = 2,
+/// 6 = 3, 7 = 5, 8+ = 11). Other Finnish word-game variants with different scoring can
+/// build their own table and reuse [`solve_ranked_with`].
+#[derive(Debug, Clone)]
+pub struct ScoreTable {
+    /// Ascending `(min_length, points)` breakpoints; a word scores the points of the
+    /// highest breakpoint whose `min_length` it meets or exceeds.
+    pub breakpoints: Vec<(usize, u32)>,
+}
+
+impl Default for ScoreTable {
+    fn default() -> Self {
+        Self {
+            breakpoints: vec![(3, 1), (5, 2), (6, 3), (7, 5), (8, 11)],
+        }
+    }
+}
+
+impl ScoreTable {
+    /// Score `word` according to this table, based on its length in characters.
+    pub fn score(&self, word: &str) -> u32 {
+        let len = word.chars().count();
+        self.breakpoints
+            .iter()
+            .rev()
+            .find(|(min_len, _)| len >= *min_len)
+            .map_or(0, |(_, points)| *points)
+    }
+}
+
+/// Score `word` using the standard Boggle length-to-points table (see [`ScoreTable`]).
+pub fn score(word: &str) -> u32 {
+    ScoreTable::default().score(word)
+}
+
+/// Like [`solve`], but sorts the found words by descending [`score`], breaking ties by
+/// length (longest first) then lexically.
+///
+/// Panics if the grid size is not 4x4.
+pub fn solve_ranked(grid: Vec<Vec<char>>) -> Vec<(String, u32)> {
+    solve_ranked_with(grid, &ScoreTable::default())
+}
+
+/// Like [`solve_ranked`], but scores words against a caller-supplied [`ScoreTable`].
+///
+/// Panics if the grid size is not 4x4.
+pub fn solve_ranked_with(grid: Vec<Vec<char>>, table: &ScoreTable) -> Vec<(String, u32)> {
+    let mut found = solve(grid)
+        .into_iter()
+        .map(|w| {
+            let score = table.score(&w);
+            (w, score)
+        })
+        .collect::<Vec<_>>();
+
+    found.sort_by(|(a, a_score), (b, b_score)| {
+        b_score
+            .cmp(a_score)
+            .then_with(|| b.chars().count().cmp(&a.chars().count()))
+            .then_with(|| a.cmp(b))
+    });
+
+    found
+}
@@ -1,14 +1,136 @@
+use std::fs;
 use std::io;
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, ValueEnum};
+use regex::Regex;
+
+/// Find every word hidden in a 4x4 letter grid, with filters and sorting over the results.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Cli {
+    /// Path to a file containing the 4x4 grid, one row per line.
+    #[arg(short, long, conflicts_with = "cells")]
+    grid: Option<PathBuf>,
+
+    /// The 4x4 grid given inline as 16 letters in row-major order (no separators), e.g.
+    /// `--cells kirjaimetaaaaaaa`. Reads 4 lines from stdin if neither this nor `--grid`
+    /// is given.
+    #[arg(long)]
+    cells: Option<String>,
+
+    /// Only keep words at least this many letters long.
+    #[arg(long)]
+    min_len: Option<usize>,
+
+    /// Only keep words at most this many letters long.
+    #[arg(long)]
+    max_len: Option<usize>,
+
+    /// Only keep words containing this substring.
+    #[arg(long)]
+    contains: Option<String>,
+
+    /// Only keep words starting with this prefix.
+    #[arg(long)]
+    starts_with: Option<String>,
+
+    /// Only keep words matching this regular expression.
+    #[arg(long)]
+    regex: Option<String>,
+
+    /// How to sort the results.
+    #[arg(long, value_enum, default_value_t = Sort::Length)]
+    sort: Sort,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Sort {
+    Length,
+    Alpha,
+    Score,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    Text,
+    Json,
+}
 
 fn main() {
-    println!("Input the grid in on 4 lines");
-    let grid = io::stdin()
-        .lines()
-        .take(4)
-        .map(|l| l.expect("Not enough lines").chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let found_vec = sanamahti::solve(grid);
-    println!("Found the following words");
-    println!("{}", found_vec.join("\n"));
+    let cli = Cli::parse();
+
+    let mut words = sanamahti::solve(read_grid(cli.grid.as_deref(), cli.cells.as_deref()));
+
+    if let Some(min_len) = cli.min_len {
+        words.retain(|w| w.chars().count() >= min_len);
+    }
+    if let Some(max_len) = cli.max_len {
+        words.retain(|w| w.chars().count() <= max_len);
+    }
+    if let Some(contains) = &cli.contains {
+        words.retain(|w| w.contains(contains.as_str()));
+    }
+    if let Some(prefix) = &cli.starts_with {
+        words.retain(|w| w.starts_with(prefix.as_str()));
+    }
+    if let Some(pattern) = &cli.regex {
+        let re = Regex::new(pattern).expect("Invalid regex");
+        words.retain(|w| re.is_match(w));
+    }
+
+    match cli.sort {
+        Sort::Length => words.sort_by_key(|w| w.chars().count()),
+        Sort::Alpha => words.sort(),
+        Sort::Score => words.sort_by(|a, b| {
+            sanamahti::score(b)
+                .cmp(&sanamahti::score(a))
+                .then_with(|| b.chars().count().cmp(&a.chars().count()))
+                .then_with(|| a.cmp(b))
+        }),
+    }
+
+    match cli.format {
+        Format::Text => {
+            println!("Found the following words");
+            println!("{}", words.join("\n"));
+        }
+        Format::Json => {
+            let entries = words
+                .iter()
+                .map(|w| format!(r#"{{"word":{w:?},"score":{}}}"#, sanamahti::score(w)))
+                .collect::<Vec<_>>()
+                .join(",");
+            println!("[{entries}]");
+        }
+    }
+}
+
+/// Read the 4x4 grid from `cells` (16 letters, row-major), or from `path` if `cells` is
+/// [`None`], or from 4 lines of stdin if both are [`None`].
+fn read_grid(path: Option<&Path>, cells: Option<&str>) -> Vec<Vec<char>> {
+    let lines = if let Some(cells) = cells {
+        let cells = cells.chars().collect::<Vec<_>>();
+        assert!(cells.len() == 16, "--cells must contain exactly 16 letters");
+        cells
+            .chunks(4)
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+    } else if let Some(path) = path {
+        let contents = fs::read_to_string(path).expect("Failed to read grid file");
+        contents.lines().take(4).map(String::from).collect::<Vec<_>>()
+    } else {
+        println!("Input the grid in on 4 lines");
+        io::stdin()
+            .lines()
+            .take(4)
+            .map(|l| l.expect("Not enough lines"))
+            .collect::<Vec<_>>()
+    };
+
+    lines.into_iter().map(|l| l.chars().collect()).collect()
 }